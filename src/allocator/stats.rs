@@ -0,0 +1,35 @@
+use super::HEAP_SIZE;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+static USED: AtomicUsize = AtomicUsize::new(0);
+static HIGH_WATER_MARK: AtomicUsize = AtomicUsize::new(0);
+
+/// A snapshot of heap usage, queried via [`stats`](super::stats).
+#[derive(Debug, Clone, Copy)]
+pub struct HeapStats {
+    pub used: usize,
+    pub free: usize,
+    pub high_water_mark: usize,
+}
+
+/// Called by the active allocator backend on every successful allocation.
+pub(super) fn record_alloc(size: usize) {
+    let used = USED.fetch_add(size, Ordering::Relaxed) + size;
+    HIGH_WATER_MARK.fetch_max(used, Ordering::Relaxed);
+}
+
+/// Called by the active allocator backend on every deallocation.
+pub(super) fn record_dealloc(size: usize) {
+    USED.fetch_sub(size, Ordering::Relaxed);
+}
+
+/// Returns the current heap usage, the remaining free space, and the
+/// high-water mark reached since `init_heap`.
+pub fn stats() -> HeapStats {
+    let used = USED.load(Ordering::Relaxed);
+    HeapStats {
+        used,
+        free: HEAP_SIZE.saturating_sub(used),
+        high_water_mark: HIGH_WATER_MARK.load(Ordering::Relaxed),
+    }
+}
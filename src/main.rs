@@ -9,7 +9,10 @@ use alloc::boxed::Box;
 use alloc::rc::Rc;
 use alloc::vec;
 use alloc::vec::Vec;
-use big_iron::memory::BootInfoFrameAllocator;
+use big_iron::memory::init_frame_allocator;
+use big_iron::task::executor::Executor;
+use big_iron::task::keyboard;
+use big_iron::task::Task;
 use big_iron::{allocator, memory, println};
 use bootloader::{BootInfo, entry_point};
 use core::panic::PanicInfo;
@@ -23,7 +26,7 @@ fn kernel_main(boot_info: &'static BootInfo) -> ! {
 
     let phys_mem_offset = VirtAddr::new(boot_info.physical_memory_offset);
     let mut mapper = unsafe { memory::init(phys_mem_offset) };
-    let mut frame_allocator = unsafe { BootInfoFrameAllocator::init(&boot_info.memory_map) };
+    let mut frame_allocator = unsafe { init_frame_allocator(&boot_info.memory_map) };
 
     allocator::init_heap(&mut mapper, &mut frame_allocator).expect("heap initialization failed");
 
@@ -36,6 +39,12 @@ fn kernel_main(boot_info: &'static BootInfo) -> ! {
     }
     println!("vec at {:p}", vec.as_slice());
 
+    let stats = allocator::stats();
+    println!(
+        "heap stats: used={} free={} high_water_mark={}",
+        stats.used, stats.free, stats.high_water_mark
+    );
+
     // create a reference counted vector -> will be freed when count reaches 0
     let reference_counted = Rc::new(vec![1, 2, 3]);
     let cloned_reference = reference_counted.clone();
@@ -53,7 +62,20 @@ fn kernel_main(boot_info: &'static BootInfo) -> ! {
     test_main();
 
     println!("It did not crash!");
-    big_iron::hlt_loop();
+
+    let mut executor = Executor::new();
+    executor.spawn(Task::new(example_task()));
+    executor.spawn(Task::new(keyboard::print_keypresses()));
+    executor.run();
+}
+
+async fn async_number() -> u32 {
+    42
+}
+
+async fn example_task() {
+    let number = async_number().await;
+    println!("async number: {}", number);
 }
 
 /// This function is called on panic.
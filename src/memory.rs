@@ -0,0 +1,109 @@
+use alloc::vec::Vec;
+use bootloader::bootinfo::{MemoryMap, MemoryRegionType};
+use x86_64::VirtAddr;
+use x86_64::structures::paging::{
+    FrameAllocator, FrameDeallocator, Mapper, OffsetPageTable, Page, PageTable, PageTableFlags,
+    PhysFrame, Size4KiB,
+};
+
+/// Initialize a new `OffsetPageTable`.
+///
+/// This function is unsafe because the caller must guarantee that the
+/// complete physical memory is mapped to virtual memory at the passed
+/// `physical_memory_offset`. Also, this function must be only called once
+/// to avoid aliasing `&mut` references (which is undefined behavior).
+pub unsafe fn init(physical_memory_offset: VirtAddr) -> OffsetPageTable<'static> {
+    let level_4_table = unsafe { active_level_4_table(physical_memory_offset) };
+    unsafe { OffsetPageTable::new(level_4_table, physical_memory_offset) }
+}
+
+/// Returns a mutable reference to the active level 4 table.
+///
+/// This function is unsafe because the caller must guarantee that the
+/// complete physical memory is mapped to virtual memory at the passed
+/// `physical_memory_offset`. Also, this function must be only called once
+/// to avoid aliasing `&mut` references (which is undefined behavior).
+unsafe fn active_level_4_table(physical_memory_offset: VirtAddr) -> &'static mut PageTable {
+    use x86_64::registers::control::Cr3;
+
+    let (level_4_table_frame, _) = Cr3::read();
+
+    let phys = level_4_table_frame.start_address();
+    let virt = physical_memory_offset + phys.as_u64();
+    let page_table_ptr: *mut PageTable = virt.as_mut_ptr();
+
+    unsafe { &mut *page_table_ptr }
+}
+
+/// Creates an example mapping for the given page to frame `0xb8000`.
+pub fn create_example_mapping(
+    page: Page,
+    mapper: &mut OffsetPageTable,
+    frame_allocator: &mut impl FrameAllocator<Size4KiB>,
+) {
+    use x86_64::structures::paging::PageTableFlags as Flags;
+
+    let frame = PhysFrame::containing_address(x86_64::PhysAddr::new(0xb8000));
+    let flags = Flags::PRESENT | Flags::WRITABLE;
+
+    let map_to_result = unsafe { mapper.map_to(page, frame, flags, frame_allocator) };
+    map_to_result.expect("map_to failed").flush();
+}
+
+/// A `FrameAllocator` that returns usable frames from the bootloader's memory map.
+///
+/// The usable-frame iterator is built once (as the concrete, unboxed type
+/// `I`) and kept as owned state, so each `allocate_frame` call is a single
+/// `next()` rather than rebuilding and re-skipping the iterator from
+/// scratch. Frames returned via `deallocate_frame` are kept on a small free
+/// stack and handed back out before pulling a new frame from the iterator.
+///
+/// `I` is generic, rather than a boxed trait object, because `init_frame_allocator` runs
+/// before `allocator::init_heap` — the frame allocator is what bootstraps
+/// the heap, so constructing it must not itself require an allocation.
+pub struct BootInfoFrameAllocator<I: Iterator<Item = PhysFrame>> {
+    frames: I,
+    free_frames: Vec<PhysFrame>,
+}
+
+/// Create a `FrameAllocator` from the passed memory map.
+///
+/// This function is unsafe because the caller must guarantee that the
+/// passed memory map is valid. The main requirement is that all frames
+/// that are marked as `USABLE` in it are really unused.
+///
+/// This is a free function rather than an associated `BootInfoFrameAllocator::init`
+/// because the returned allocator is generic over its iterator's concrete
+/// (unnameable) type; an inherent `impl<I> BootInfoFrameAllocator<I>` block
+/// can't express a constructor whose `I` isn't determined by its arguments.
+pub unsafe fn init_frame_allocator(
+    memory_map: &'static MemoryMap,
+) -> BootInfoFrameAllocator<impl Iterator<Item = PhysFrame>> {
+    BootInfoFrameAllocator {
+        frames: usable_frames(memory_map),
+        free_frames: Vec::new(),
+    }
+}
+
+/// Returns an iterator over the usable frames specified in the memory map.
+fn usable_frames(memory_map: &'static MemoryMap) -> impl Iterator<Item = PhysFrame> {
+    let regions = memory_map.iter();
+    let usable_regions = regions.filter(|r| r.region_type == MemoryRegionType::Usable);
+    let addr_ranges = usable_regions.map(|r| r.range.start_addr()..r.range.end_addr());
+    let frame_addresses = addr_ranges.flat_map(|r| r.step_by(4096));
+    frame_addresses.map(|addr| PhysFrame::containing_address(x86_64::PhysAddr::new(addr)))
+}
+
+unsafe impl<I: Iterator<Item = PhysFrame>> FrameAllocator<Size4KiB> for BootInfoFrameAllocator<I> {
+    fn allocate_frame(&mut self) -> Option<PhysFrame> {
+        self.free_frames.pop().or_else(|| self.frames.next())
+    }
+}
+
+unsafe impl<I: Iterator<Item = PhysFrame>> FrameDeallocator<Size4KiB>
+    for BootInfoFrameAllocator<I>
+{
+    unsafe fn deallocate_frame(&mut self, frame: PhysFrame) {
+        self.free_frames.push(frame);
+    }
+}